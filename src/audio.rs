@@ -0,0 +1,139 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleRate, Stream, StreamConfig};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+// Click volume and duration, in samples at whatever the output device's
+// sample rate turns out to be. Short enough to read as a tick, not a beep.
+const CLICK_AMPLITUDE: f32 = 0.2;
+const CLICK_DUTY_CYCLE: f32 = 0.5;
+const CLICK_DURATION_SECS: f32 = 0.01;
+
+// Frequencies for the two distinct cues: one per in-game frame boundary,
+// and a higher one when a tracked input's `frames_held` lands on an exact
+// integer frame count.
+const FRAME_TICK_HZ: f32 = 220.0;
+const EXACT_FRAME_HZ: f32 = 880.0;
+
+/// NES-APU-style square wave generator: a duty cycle toggling the output
+/// between high and low amplitude at a timer period derived from the
+/// desired tone frequency.
+fn generate_square_wave(sample_rate: u32, freq_hz: f32, duty_cycle: f32, duration_secs: f32) -> Vec<f32> {
+    let period_samples = (sample_rate as f32 / freq_hz).round().max(1.0) as u32;
+    let high_samples = (period_samples as f32 * duty_cycle).round() as u32;
+    let total_samples = (sample_rate as f32 * duration_secs).round() as u32;
+
+    (0..total_samples)
+        .map(|i| {
+            if i % period_samples < high_samples {
+                CLICK_AMPLITUDE
+            } else {
+                -CLICK_AMPLITUDE
+            }
+        })
+        .collect()
+}
+
+/// Optional audio subsystem emitting a click once per in-game frame, plus a
+/// distinct tone when a tracked input's `frames_held` lands on an integer
+/// frame count. Ticks are scheduled off the same `Instant` clock
+/// `maybe_poll` uses rather than the render loop, so they stay sample-
+/// accurate regardless of how often `render` actually runs.
+pub struct Metronome {
+    enabled: Arc<AtomicBool>,
+    ring: Arc<Mutex<VecDeque<f32>>>,
+    sample_rate: u32,
+    last_frame_tick: Instant,
+    // Kept alive for as long as the metronome should keep playing; dropping
+    // it stops the output stream.
+    _stream: Stream,
+}
+
+impl Metronome {
+    pub fn new() -> Option<Self> {
+        let host = cpal::default_host();
+        let device = host.default_output_device()?;
+        let supported = device.default_output_config().ok()?;
+        let sample_rate = supported.sample_rate().0;
+        let config: StreamConfig = StreamConfig {
+            channels: 1,
+            sample_rate: SampleRate(sample_rate),
+            buffer_size: cpal::BufferSize::Default,
+        };
+
+        let enabled = Arc::new(AtomicBool::new(false));
+        let ring = Arc::new(Mutex::new(VecDeque::new()));
+
+        let stream_ring = ring.clone();
+        let stream = device
+            .build_output_stream(
+                &config,
+                move |data: &mut [f32], _| {
+                    let mut ring = stream_ring.lock().unwrap();
+                    for sample in data.iter_mut() {
+                        *sample = ring.pop_front().unwrap_or(0.0);
+                    }
+                },
+                |err| eprintln!("audio stream error: {err}"),
+                None,
+            )
+            .ok()?;
+        stream.play().ok()?;
+
+        Some(Metronome {
+            enabled,
+            ring,
+            sample_rate,
+            last_frame_tick: Instant::now(),
+            _stream: stream,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn toggle(&self) {
+        self.set_enabled(!self.is_enabled());
+    }
+
+    /// Call every `maybe_poll` tick. Emits a frame-boundary click exactly
+    /// once per `nanos_per_frame`, scheduled off `now` rather than however
+    /// often this happens to be called.
+    pub fn maybe_tick_frame(&mut self, now: Instant, nanos_per_frame: u128) {
+        if !self.is_enabled() {
+            self.last_frame_tick = now;
+            return;
+        }
+
+        if now.duration_since(self.last_frame_tick).as_nanos() >= nanos_per_frame {
+            self.push_click(FRAME_TICK_HZ);
+            self.last_frame_tick = now;
+        }
+    }
+
+    /// Plays the distinct "exact frame" cue, e.g. when a tracked input's
+    /// `frames_held` lands on an integer frame count.
+    pub fn tick_exact_frame(&self) {
+        if self.is_enabled() {
+            self.push_click(EXACT_FRAME_HZ);
+        }
+    }
+
+    fn push_click(&self, freq_hz: f32) {
+        let samples = generate_square_wave(
+            self.sample_rate,
+            freq_hz,
+            CLICK_DUTY_CYCLE,
+            CLICK_DURATION_SECS,
+        );
+        let mut ring = self.ring.lock().unwrap();
+        ring.extend(samples);
+    }
+}
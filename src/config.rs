@@ -0,0 +1,179 @@
+use crate::input::InputBinding;
+use crate::{customize, init_and_run_with_config};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const CONFIG_PATH: &str = "v6prac.toml";
+
+pub const DEFAULT_NANOS_PER_FRAME: u64 = 34_000_000;
+
+/// A named binding set for a different game/control scheme, beyond the
+/// `"Axes"`/`"Raw"` pair `build_layouts` always derives from `bindings`.
+/// There's no in-app editor for these yet — add a `[[extra_layouts]]` table
+/// to `v6prac.toml` by hand and it's loaded (and reachable with the layout
+/// switch hotkey) on the next launch, no rebuild needed.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LayoutConfig {
+    pub name: String,
+    pub bindings: Vec<InputBinding>,
+}
+
+/// Everything `customize()` used to throw away at the end of every run:
+/// which keys/buttons to track, and the per-game timing constants that used
+/// to be hardcoded `const`s.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AppConfig {
+    pub bindings: Vec<InputBinding>,
+    // `u64`, not `u128`: `toml`/`toml_edit` has no 128-bit integer support, so
+    // a `u128` field here silently breaks both `save()` and `load()`. Widened
+    // back to `u128` only where `main.rs` actually needs it (alongside
+    // `frames_held`, which counts in nanoseconds over a long poll history).
+    pub nanos_per_frame: u64,
+    /// Whether the swap chain should wait for the display's refresh (real
+    /// vsync) or swap as soon as a frame is ready. Polling always runs flat
+    /// out regardless of this setting; it only governs render cadence.
+    pub vsync: bool,
+    /// Additional named layouts loaded alongside the derived `"Axes"`/`"Raw"`
+    /// pair. Empty for anyone who hasn't hand-edited the config.
+    #[serde(default)]
+    pub extra_layouts: Vec<LayoutConfig>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            bindings: Vec::new(),
+            nanos_per_frame: DEFAULT_NANOS_PER_FRAME,
+            vsync: true,
+            extra_layouts: Vec::new(),
+        }
+    }
+}
+
+/// Loads the config from `v6prac.toml` next to the binary, if present.
+pub fn load() -> Option<AppConfig> {
+    if !Path::new(CONFIG_PATH).exists() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(CONFIG_PATH).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// Writes the config back to `v6prac.toml`, overwriting any existing file.
+pub fn save(config: &AppConfig) -> std::io::Result<()> {
+    let contents = toml::to_string_pretty(config).map_err(std::io::Error::other)?;
+    std::fs::write(CONFIG_PATH, contents)
+}
+
+/// Builder for the app, mirroring the common `AppBuilder` pattern: set only
+/// what you care about, anything left unset falls back to `AppConfig`'s
+/// defaults. `V6Prac::builder().with_frame_millis(34).with_keys(keys).build()`
+/// skips the interactive `customize()` step entirely, which is also how
+/// `main()` launches once a config has been loaded from disk.
+///
+/// Nothing in this binary calls it yet (`main()` goes through
+/// `load_or_customize`/`recustomize` instead) — it's here as the programmatic
+/// equivalent of those for whoever embeds `v6prac` and wants to skip the
+/// interactive flow entirely, so `#[allow(dead_code)]` rather than deleting it.
+#[allow(dead_code)]
+#[derive(Default)]
+pub struct V6PracBuilder {
+    bindings: Option<Vec<InputBinding>>,
+    nanos_per_frame: Option<u64>,
+    vsync: Option<bool>,
+    title: Option<String>,
+}
+
+#[allow(dead_code)]
+impl V6PracBuilder {
+    pub fn with_keys(mut self, bindings: Vec<InputBinding>) -> Self {
+        self.bindings = Some(bindings);
+        self
+    }
+
+    pub fn with_frame_millis(mut self, millis: u64) -> Self {
+        self.nanos_per_frame = Some(millis * 1_000_000);
+        self
+    }
+
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.vsync = Some(vsync);
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn build(self) -> V6Prac {
+        let defaults = AppConfig::default();
+        V6Prac {
+            config: AppConfig {
+                bindings: self.bindings.unwrap_or(defaults.bindings),
+                nanos_per_frame: self.nanos_per_frame.unwrap_or(defaults.nanos_per_frame),
+                vsync: self.vsync.unwrap_or(defaults.vsync),
+                extra_layouts: defaults.extra_layouts,
+            },
+            title: self.title.unwrap_or_else(|| "v6prac".to_string()),
+        }
+    }
+}
+
+pub struct V6Prac {
+    config: AppConfig,
+    title: String,
+}
+
+impl V6Prac {
+    // Same story as `V6PracBuilder`: this binary doesn't call it, but it's
+    // the entry point embedders use instead of `load_or_customize`.
+    #[allow(dead_code)]
+    pub fn builder() -> V6PracBuilder {
+        V6PracBuilder::default()
+    }
+
+    /// Loads `v6prac.toml` if present, otherwise runs the interactive
+    /// `customize()` flow and writes the result back so future launches
+    /// skip straight to tracking.
+    pub fn load_or_customize() -> V6Prac {
+        let config = load().unwrap_or_else(|| {
+            let bindings = customize();
+            let config = AppConfig {
+                bindings,
+                ..AppConfig::default()
+            };
+            if let Err(e) = save(&config) {
+                println!("Couldn't write {}: {}", CONFIG_PATH, e);
+            }
+            config
+        });
+
+        V6Prac {
+            config,
+            title: "v6prac".to_string(),
+        }
+    }
+
+    /// Forces the interactive `customize()` flow regardless of any existing
+    /// config file, then overwrites it with the result.
+    pub fn recustomize() -> V6Prac {
+        let bindings = customize();
+        let config = AppConfig {
+            bindings,
+            ..AppConfig::default()
+        };
+        if let Err(e) = save(&config) {
+            println!("Couldn't write {}: {}", CONFIG_PATH, e);
+        }
+
+        V6Prac {
+            config,
+            title: "v6prac".to_string(),
+        }
+    }
+
+    pub fn run(self) {
+        init_and_run_with_config(self.config, self.title);
+    }
+}
@@ -0,0 +1,150 @@
+use super::{InputBinding, InputSource};
+use device_query::Keycode;
+use evdev_rs::enums::{EventCode, EV_KEY};
+use evdev_rs::{Device, ReadFlag};
+use std::collections::HashMap;
+use std::fs::File;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Alternative to `KeyboardSource` that opens a `/dev/input/event*` keyboard
+/// device directly and reads raw kernel timestamps, instead of sampling key
+/// state at whatever rate the render/poll loop happens to run at. This is
+/// what lets `RecordedPoll` store true press/release instants rather than
+/// `dt_before`/`dt_after` estimates. Only built when the `evdev_backend`
+/// feature is enabled, and only usable on Linux with read access to the
+/// device node (typically requires being in the `input` group).
+pub struct EvdevSource {
+    device: Device,
+    pressed: HashMap<EventCode, Instant>,
+}
+
+impl EvdevSource {
+    /// Looks for the first `/dev/input/event*` device that reports key
+    /// events and opens it. Returns `None` (rather than erroring) so the
+    /// caller can fall back to `KeyboardSource`.
+    pub fn new() -> Option<Self> {
+        for entry in std::fs::read_dir("/dev/input").ok()? {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            if !path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("event"))
+            {
+                continue;
+            }
+
+            // Most `/dev/input/event*` nodes aren't keyboards (mice,
+            // touchpads, ...) and some aren't readable by this user at all —
+            // neither should stop the scan from trying the rest.
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            let Ok(device) = Device::new_from_file(file) else {
+                continue;
+            };
+            if device.has_event_code(&EventCode::EV_KEY(EV_KEY::KEY_A)) {
+                return Some(EvdevSource {
+                    device,
+                    pressed: HashMap::new(),
+                });
+            }
+        }
+        None
+    }
+}
+
+impl InputSource for EvdevSource {
+    fn poll_pressed(&mut self) -> Vec<InputBinding> {
+        // No `BLOCKING`: once the pending event queue drains, `next_event`
+        // returns `EAGAIN` instead of waiting for the next kernel event,
+        // which would otherwise stall the whole poll/render loop.
+        while let Ok((_, event)) = self.device.next_event(ReadFlag::NORMAL) {
+            if let EventCode::EV_KEY(_) = event.event_code {
+                let instant = system_time_to_instant(event.time.as_raw());
+                match event.value {
+                    1 => {
+                        self.pressed.insert(event.event_code, instant);
+                    }
+                    0 => {
+                        self.pressed.remove(&event.event_code);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.pressed
+            .keys()
+            .filter_map(|code| event_code_to_keycode(code))
+            .map(InputBinding::Key)
+            .collect()
+    }
+
+    fn precise_event_instant(&self, binding: &InputBinding) -> Option<Instant> {
+        let InputBinding::Key(keycode) = binding else {
+            return None;
+        };
+        self.pressed
+            .iter()
+            .find(|(code, _)| event_code_to_keycode(code) == Some(*keycode))
+            .map(|(_, instant)| *instant)
+    }
+}
+
+fn system_time_to_instant(raw: libc::timeval) -> Instant {
+    let event_time = SystemTime::UNIX_EPOCH
+        + Duration::new(raw.tv_sec as u64, raw.tv_usec as u32 * 1000);
+    let now_system = SystemTime::now();
+    let now_instant = Instant::now();
+    match now_system.duration_since(event_time) {
+        Ok(elapsed) => now_instant - elapsed,
+        Err(_) => now_instant,
+    }
+}
+
+/// Only the subset of keys `customize()`/`layout::keycode_to_display` already
+/// knows how to display is mapped; anything else is ignored the same way
+/// unsupported keys are already rejected during customization.
+fn event_code_to_keycode(code: &EventCode) -> Option<Keycode> {
+    let EventCode::EV_KEY(key) = code else {
+        return None;
+    };
+    let keycode = match key {
+        EV_KEY::KEY_A => Keycode::A,
+        EV_KEY::KEY_B => Keycode::B,
+        EV_KEY::KEY_C => Keycode::C,
+        EV_KEY::KEY_D => Keycode::D,
+        EV_KEY::KEY_E => Keycode::E,
+        EV_KEY::KEY_F => Keycode::F,
+        EV_KEY::KEY_G => Keycode::G,
+        EV_KEY::KEY_H => Keycode::H,
+        EV_KEY::KEY_I => Keycode::I,
+        EV_KEY::KEY_J => Keycode::J,
+        EV_KEY::KEY_K => Keycode::K,
+        EV_KEY::KEY_L => Keycode::L,
+        EV_KEY::KEY_M => Keycode::M,
+        EV_KEY::KEY_N => Keycode::N,
+        EV_KEY::KEY_O => Keycode::O,
+        EV_KEY::KEY_P => Keycode::P,
+        EV_KEY::KEY_Q => Keycode::Q,
+        EV_KEY::KEY_R => Keycode::R,
+        EV_KEY::KEY_S => Keycode::S,
+        EV_KEY::KEY_T => Keycode::T,
+        EV_KEY::KEY_U => Keycode::U,
+        EV_KEY::KEY_V => Keycode::V,
+        EV_KEY::KEY_W => Keycode::W,
+        EV_KEY::KEY_X => Keycode::X,
+        EV_KEY::KEY_Y => Keycode::Y,
+        EV_KEY::KEY_Z => Keycode::Z,
+        EV_KEY::KEY_UP => Keycode::Up,
+        EV_KEY::KEY_DOWN => Keycode::Down,
+        EV_KEY::KEY_LEFT => Keycode::Left,
+        EV_KEY::KEY_RIGHT => Keycode::Right,
+        EV_KEY::KEY_BACKSPACE => Keycode::Backspace,
+        _ => return None,
+    };
+    Some(keycode)
+}
@@ -0,0 +1,73 @@
+use super::{GamepadInput, InputBinding, InputSource};
+use gilrs::{Axis, Button, Gilrs};
+
+/// Threshold past which an analog axis counts as "pressed" in a direction.
+const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+const ALL_BUTTONS: &[Button] = &[
+    Button::South,
+    Button::East,
+    Button::North,
+    Button::West,
+    Button::LeftTrigger,
+    Button::LeftTrigger2,
+    Button::RightTrigger,
+    Button::RightTrigger2,
+    Button::Select,
+    Button::Start,
+    Button::Mode,
+    Button::LeftThumb,
+    Button::RightThumb,
+    Button::DPadUp,
+    Button::DPadDown,
+    Button::DPadLeft,
+    Button::DPadRight,
+];
+
+const ALL_AXES: &[Axis] = &[
+    Axis::LeftStickX,
+    Axis::LeftStickY,
+    Axis::RightStickX,
+    Axis::RightStickY,
+];
+
+/// Polls every connected gamepad via `gilrs`. Unlike `KeyboardSource`, this
+/// needs a bit of internal state (the `Gilrs` context) to see connected pads
+/// and drain their event queue.
+pub struct GamepadSource {
+    gilrs: Gilrs,
+}
+
+impl GamepadSource {
+    pub fn new() -> Option<Self> {
+        Gilrs::new().ok().map(|gilrs| GamepadSource { gilrs })
+    }
+}
+
+impl InputSource for GamepadSource {
+    fn poll_pressed(&mut self) -> Vec<InputBinding> {
+        // Drain the event queue; we only care about current button/axis
+        // state below, not the individual press/release events.
+        while self.gilrs.next_event().is_some() {}
+
+        let mut pressed = Vec::new();
+        for (_id, gamepad) in self.gilrs.gamepads() {
+            for button in ALL_BUTTONS {
+                if gamepad.is_pressed(*button) {
+                    pressed.push(InputBinding::Gamepad(GamepadInput::Button(*button)));
+                }
+            }
+            for axis in ALL_AXES {
+                if let Some(data) = gamepad.axis_data(*axis) {
+                    let value = data.value();
+                    if value >= AXIS_PRESS_THRESHOLD {
+                        pressed.push(InputBinding::Gamepad(GamepadInput::AxisPositive(*axis)));
+                    } else if value <= -AXIS_PRESS_THRESHOLD {
+                        pressed.push(InputBinding::Gamepad(GamepadInput::AxisNegative(*axis)));
+                    }
+                }
+            }
+        }
+        pressed
+    }
+}
@@ -0,0 +1,26 @@
+use super::{InputBinding, InputSource};
+use device_query::{DeviceQuery, DeviceState};
+
+/// Polls the keyboard via `device_query`. Coarse polling granularity and it
+/// can't see Backspace, but it works everywhere without extra permissions.
+pub struct KeyboardSource {
+    device_state: DeviceState,
+}
+
+impl KeyboardSource {
+    pub fn new() -> Self {
+        KeyboardSource {
+            device_state: DeviceState::new(),
+        }
+    }
+}
+
+impl InputSource for KeyboardSource {
+    fn poll_pressed(&mut self) -> Vec<InputBinding> {
+        self.device_state
+            .get_keys()
+            .into_iter()
+            .map(InputBinding::Key)
+            .collect()
+    }
+}
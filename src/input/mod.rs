@@ -0,0 +1,133 @@
+mod gamepad;
+mod keyboard;
+
+#[cfg(feature = "evdev_backend")]
+mod evdev;
+
+use crate::layout;
+use device_query::Keycode;
+use gilrs::{Axis, Button};
+use serde::{Deserialize, Serialize};
+
+pub use gamepad::GamepadSource;
+pub use keyboard::KeyboardSource;
+
+#[cfg(feature = "evdev_backend")]
+pub use evdev::EvdevSource;
+
+/// A single button/axis-direction from a gamepad, generalized the same way
+/// `Keycode` represents a single key. Serializable because gilrs builds
+/// `Button`/`Axis` with its `serde-serialize` feature enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GamepadInput {
+    Button(Button),
+    AxisPositive(Axis),
+    AxisNegative(Axis),
+}
+
+/// Anything an `Action` can be bound to: a keyboard key or a gamepad
+/// button/axis direction. Serializable so a tracked set of bindings can be
+/// persisted to the config file instead of re-bound on every launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputBinding {
+    Key(Keycode),
+    Gamepad(GamepadInput),
+}
+
+/// `device_query::Keycode` doesn't implement `Serialize`/`Deserialize` (and
+/// the orphan rule means this crate can't add the impl directly), so
+/// `InputBinding` is serialized by hand through this mirror shape instead,
+/// storing `Keycode` as the canonical name from `layout::keycode_name`.
+#[derive(Serialize, Deserialize)]
+enum InputBindingRepr {
+    Key(String),
+    Gamepad(GamepadInput),
+}
+
+impl Serialize for InputBinding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            InputBinding::Key(keycode) => {
+                let name = layout::keycode_name(keycode).ok_or_else(|| {
+                    serde::ser::Error::custom(format!("unsupported key {keycode:?}"))
+                })?;
+                InputBindingRepr::Key(name.to_string())
+            }
+            InputBinding::Gamepad(gamepad) => InputBindingRepr::Gamepad(*gamepad),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for InputBinding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match InputBindingRepr::deserialize(deserializer)? {
+            InputBindingRepr::Key(name) => layout::keycode_from_name(&name)
+                .map(InputBinding::Key)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown key {name:?}"))),
+            InputBindingRepr::Gamepad(gamepad) => Ok(InputBinding::Gamepad(gamepad)),
+        }
+    }
+}
+
+/// A source of `InputBinding`s that can be polled for what's currently held.
+/// `KeyboardSource`, `GamepadSource`, and (when the `evdev_backend` feature
+/// is enabled) `EvdevSource` all implement this so the rest of the app
+/// doesn't need to care where a binding came from.
+pub trait InputSource {
+    fn poll_pressed(&mut self) -> Vec<InputBinding>;
+
+    /// The kernel-reported instant a binding last changed press state, if
+    /// this source can see that directly (e.g. `evdev`) instead of only
+    /// knowing it through the poll loop's own sampling.
+    fn precise_event_instant(&self, _binding: &InputBinding) -> Option<std::time::Instant> {
+        None
+    }
+}
+
+/// Short label for a gamepad binding, used the same way `keycode_to_char`
+/// labels a keyboard key.
+pub fn gamepad_input_to_char(input: &GamepadInput) -> Option<char> {
+    match input {
+        GamepadInput::Button(Button::South) => Some('A'),
+        GamepadInput::Button(Button::East) => Some('B'),
+        GamepadInput::Button(Button::North) => Some('X'),
+        GamepadInput::Button(Button::West) => Some('Y'),
+        GamepadInput::Button(Button::LeftTrigger) => Some('L'),
+        GamepadInput::Button(Button::RightTrigger) => Some('R'),
+        GamepadInput::Button(Button::Select) => Some('-'),
+        GamepadInput::Button(Button::Start) => Some('+'),
+        GamepadInput::Button(Button::DPadUp) => Some('^'),
+        GamepadInput::Button(Button::DPadDown) => Some('^'),
+        GamepadInput::Button(Button::DPadLeft) => Some('^'),
+        GamepadInput::Button(Button::DPadRight) => Some('^'),
+        GamepadInput::AxisPositive(_) | GamepadInput::AxisNegative(_) => Some('^'),
+        _ => None,
+    }
+}
+
+/// Builds the default set of input sources for this platform: keyboard and
+/// gamepad always, plus the `evdev` backend in place of `device_query` when
+/// the `evdev_backend` feature is enabled and a keyboard device can be
+/// opened.
+pub fn default_sources() -> Vec<Box<dyn InputSource>> {
+    let mut sources: Vec<Box<dyn InputSource>> = Vec::new();
+
+    #[cfg(feature = "evdev_backend")]
+    {
+        match EvdevSource::new() {
+            Some(evdev) => sources.push(Box::new(evdev)),
+            None => sources.push(Box::new(KeyboardSource::new())),
+        }
+    }
+    #[cfg(not(feature = "evdev_backend"))]
+    {
+        sources.push(Box::new(KeyboardSource::new()));
+    }
+
+    if let Some(gamepad) = GamepadSource::new() {
+        sources.push(Box::new(gamepad));
+    }
+
+    sources
+}
@@ -0,0 +1,501 @@
+use crate::config::LayoutConfig;
+use crate::input::{gamepad_input_to_char, InputBinding};
+use device_query::Keycode;
+
+/// How a single glyph is drawn: upright, rotated around its own center (used
+/// for the arrow-direction glyphs), or as a short multi-character label for
+/// keys that don't have a natural single-character glyph (e.g. `"Tab"`,
+/// `"F12"`).
+pub enum DisplayChar {
+    Simple(char),
+    Rotated(f32, char),
+    Label(&'static str),
+}
+
+/// What an `Action` is bound to and how it's displayed.
+pub enum ActionKind {
+    /// A plain on/off input, e.g. a single key or gamepad button.
+    Button {
+        binding: InputBinding,
+        display: DisplayChar,
+    },
+    /// A negative/positive key pair collapsed into one row, e.g. Left/Right
+    /// or Up/Down. Renders as a single glyph whose sign reflects which side
+    /// (if either) is currently held.
+    Axis {
+        negative: InputBinding,
+        positive: InputBinding,
+        negative_display: DisplayChar,
+        positive_display: DisplayChar,
+    },
+}
+
+/// A single logical, named input the player cares about — what used to be
+/// a `DisplayableAction` bound straight to one key.
+pub struct Action {
+    pub name: String,
+    pub kind: ActionKind,
+}
+
+/// A named, ordered set of actions. Multiple layouts can be loaded at once
+/// and swapped at runtime (e.g. with a hotkey) without losing the others.
+pub struct Layout {
+    pub name: String,
+    pub actions: Vec<Action>,
+}
+
+/// Builds the two layouts derivable from a flat list of press-to-bind
+/// bindings: `"Raw"`, where every binding is its own button, and `"Axes"`,
+/// where Up/Down and Left/Right keyboard pairs are collapsed into vertical
+/// and horizontal axis actions. Everything else (gamepad bindings, unpaired
+/// direction keys) stays a button in both.
+///
+/// `extra` appends one further `"Raw"`-style layout per `LayoutConfig` loaded
+/// from `v6prac.toml` (see `AppConfig::extra_layouts`) — e.g. a different
+/// game's binding set — all reachable at runtime with the same layout-switch
+/// hotkey as `"Axes"`/`"Raw"`.
+pub fn build_layouts(bindings: Vec<InputBinding>, extra: &[LayoutConfig]) -> Vec<Layout> {
+    let mut layouts = vec![
+        Layout {
+            name: "Axes".to_string(),
+            actions: build_axis_actions(&bindings),
+        },
+        Layout {
+            name: "Raw".to_string(),
+            actions: build_raw_actions(&bindings),
+        },
+    ];
+    for layout_config in extra {
+        layouts.push(Layout {
+            name: layout_config.name.clone(),
+            actions: build_raw_actions(&layout_config.bindings),
+        });
+    }
+    layouts
+}
+
+fn build_raw_actions(bindings: &[InputBinding]) -> Vec<Action> {
+    bindings
+        .iter()
+        .map(|binding| Action {
+            name: format!("{:?}", binding),
+            kind: ActionKind::Button {
+                binding: *binding,
+                display: display_char_for(binding),
+            },
+        })
+        .collect()
+}
+
+fn build_axis_actions(bindings: &[InputBinding]) -> Vec<Action> {
+    let has = |k: Keycode| bindings.contains(&InputBinding::Key(k));
+
+    let mut actions = Vec::new();
+    let mut consumed = Vec::new();
+
+    if has(Keycode::Up) && has(Keycode::Down) {
+        actions.push(Action {
+            name: "Vertical".to_string(),
+            kind: ActionKind::Axis {
+                negative: InputBinding::Key(Keycode::Down),
+                positive: InputBinding::Key(Keycode::Up),
+                negative_display: DisplayChar::Rotated(std::f32::consts::PI, '^'),
+                positive_display: DisplayChar::Simple('^'),
+            },
+        });
+        consumed.push(InputBinding::Key(Keycode::Up));
+        consumed.push(InputBinding::Key(Keycode::Down));
+    }
+
+    if has(Keycode::Left) && has(Keycode::Right) {
+        actions.push(Action {
+            name: "Horizontal".to_string(),
+            kind: ActionKind::Axis {
+                negative: InputBinding::Key(Keycode::Left),
+                positive: InputBinding::Key(Keycode::Right),
+                negative_display: DisplayChar::Rotated(-std::f32::consts::FRAC_PI_2, '^'),
+                positive_display: DisplayChar::Rotated(std::f32::consts::FRAC_PI_2, '^'),
+            },
+        });
+        consumed.push(InputBinding::Key(Keycode::Left));
+        consumed.push(InputBinding::Key(Keycode::Right));
+    }
+
+    for binding in bindings {
+        if consumed.contains(binding) {
+            continue;
+        }
+        actions.push(Action {
+            name: format!("{:?}", binding),
+            kind: ActionKind::Button {
+                binding: *binding,
+                display: display_char_for(binding),
+            },
+        });
+    }
+
+    actions
+}
+
+fn display_char_for(binding: &InputBinding) -> DisplayChar {
+    binding_to_display(binding).unwrap()
+}
+
+/// The glyph a binding would be drawn with, or `None` if this binding isn't
+/// supported — used by `customize()` to reject bindings it doesn't know how
+/// to render.
+pub fn binding_to_display(binding: &InputBinding) -> Option<DisplayChar> {
+    match binding {
+        InputBinding::Key(k) => keycode_to_display(k),
+        InputBinding::Gamepad(g) => gamepad_input_to_char(g).map(DisplayChar::Simple),
+    }
+}
+
+/// The glyph (or short label) for a single key. Arrow keys come out rotated
+/// so the same `^` reads as up/down/left/right; everything else is either a
+/// single character or, for keys without one (`Space`, `Tab`, `F1`-`F20`,
+/// ...), a short text label drawn with `draw_text_at_pos` instead of
+/// `draw_char_at_pos`.
+fn keycode_to_display(keycode: &Keycode) -> Option<DisplayChar> {
+    match keycode {
+        Keycode::Up => Some(DisplayChar::Simple('^')),
+        Keycode::Down => Some(DisplayChar::Rotated(std::f32::consts::PI, '^')),
+        Keycode::Left => Some(DisplayChar::Rotated(-std::f32::consts::FRAC_PI_2, '^')),
+        Keycode::Right => Some(DisplayChar::Rotated(std::f32::consts::FRAC_PI_2, '^')),
+
+        Keycode::Space => Some(DisplayChar::Label("SP")),
+        Keycode::Tab => Some(DisplayChar::Label("TAB")),
+        Keycode::Enter => Some(DisplayChar::Label("ENT")),
+        Keycode::Escape => Some(DisplayChar::Label("ESC")),
+        Keycode::CapsLock => Some(DisplayChar::Label("CAPS")),
+        Keycode::Home => Some(DisplayChar::Label("HOME")),
+        Keycode::End => Some(DisplayChar::Label("END")),
+        Keycode::PageUp => Some(DisplayChar::Label("PGUP")),
+        Keycode::PageDown => Some(DisplayChar::Label("PGDN")),
+        Keycode::Insert => Some(DisplayChar::Label("INS")),
+        Keycode::Delete => Some(DisplayChar::Label("DEL")),
+
+        Keycode::F1 => Some(DisplayChar::Label("F1")),
+        Keycode::F2 => Some(DisplayChar::Label("F2")),
+        Keycode::F3 => Some(DisplayChar::Label("F3")),
+        Keycode::F4 => Some(DisplayChar::Label("F4")),
+        Keycode::F5 => Some(DisplayChar::Label("F5")),
+        Keycode::F6 => Some(DisplayChar::Label("F6")),
+        Keycode::F7 => Some(DisplayChar::Label("F7")),
+        Keycode::F8 => Some(DisplayChar::Label("F8")),
+        Keycode::F9 => Some(DisplayChar::Label("F9")),
+        Keycode::F10 => Some(DisplayChar::Label("F10")),
+        Keycode::F11 => Some(DisplayChar::Label("F11")),
+        Keycode::F12 => Some(DisplayChar::Label("F12")),
+        Keycode::F13 => Some(DisplayChar::Label("F13")),
+        Keycode::F14 => Some(DisplayChar::Label("F14")),
+        Keycode::F15 => Some(DisplayChar::Label("F15")),
+        Keycode::F16 => Some(DisplayChar::Label("F16")),
+        Keycode::F17 => Some(DisplayChar::Label("F17")),
+        Keycode::F18 => Some(DisplayChar::Label("F18")),
+        Keycode::F19 => Some(DisplayChar::Label("F19")),
+        Keycode::F20 => Some(DisplayChar::Label("F20")),
+
+        _ => keycode_to_char(keycode).map(DisplayChar::Simple),
+    }
+}
+
+/// `device_query::Keycode` has no serde support of its own (and the orphan
+/// rule means this crate can't add one), so `InputBinding`'s manual
+/// `Serialize`/`Deserialize` impl in `input::mod` round-trips a `Keycode`
+/// through this canonical name instead. Only covers the keys
+/// `keycode_to_display` already knows how to show, since those are the only
+/// ones `customize()` ever lets through to be persisted.
+pub fn keycode_name(keycode: &Keycode) -> Option<&'static str> {
+    let name = match keycode {
+        Keycode::Key0 => "Key0",
+        Keycode::Key1 => "Key1",
+        Keycode::Key2 => "Key2",
+        Keycode::Key3 => "Key3",
+        Keycode::Key4 => "Key4",
+        Keycode::Key5 => "Key5",
+        Keycode::Key6 => "Key6",
+        Keycode::Key7 => "Key7",
+        Keycode::Key8 => "Key8",
+        Keycode::Key9 => "Key9",
+
+        Keycode::A => "A",
+        Keycode::B => "B",
+        Keycode::C => "C",
+        Keycode::D => "D",
+        Keycode::E => "E",
+        Keycode::F => "F",
+        Keycode::G => "G",
+        Keycode::H => "H",
+        Keycode::I => "I",
+        Keycode::J => "J",
+        Keycode::K => "K",
+        Keycode::L => "L",
+        Keycode::M => "M",
+        Keycode::N => "N",
+        Keycode::O => "O",
+        Keycode::P => "P",
+        Keycode::Q => "Q",
+        Keycode::R => "R",
+        Keycode::S => "S",
+        Keycode::T => "T",
+        Keycode::U => "U",
+        Keycode::V => "V",
+        Keycode::W => "W",
+        Keycode::X => "X",
+        Keycode::Y => "Y",
+        Keycode::Z => "Z",
+
+        Keycode::Up => "Up",
+        Keycode::Down => "Down",
+        Keycode::Left => "Left",
+        Keycode::Right => "Right",
+
+        Keycode::Numpad0 => "Numpad0",
+        Keycode::Numpad1 => "Numpad1",
+        Keycode::Numpad2 => "Numpad2",
+        Keycode::Numpad3 => "Numpad3",
+        Keycode::Numpad4 => "Numpad4",
+        Keycode::Numpad5 => "Numpad5",
+        Keycode::Numpad6 => "Numpad6",
+        Keycode::Numpad7 => "Numpad7",
+        Keycode::Numpad8 => "Numpad8",
+        Keycode::Numpad9 => "Numpad9",
+        Keycode::NumpadSubtract => "NumpadSubtract",
+        Keycode::NumpadAdd => "NumpadAdd",
+        Keycode::NumpadDivide => "NumpadDivide",
+        Keycode::NumpadMultiply => "NumpadMultiply",
+        Keycode::Grave => "Grave",
+        Keycode::Minus => "Minus",
+        Keycode::Equal => "Equal",
+        Keycode::LeftBracket => "LeftBracket",
+        Keycode::RightBracket => "RightBracket",
+        Keycode::BackSlash => "BackSlash",
+        Keycode::Semicolon => "Semicolon",
+        Keycode::Apostrophe => "Apostrophe",
+        Keycode::Comma => "Comma",
+        Keycode::Dot => "Dot",
+        Keycode::Slash => "Slash",
+
+        Keycode::Space => "Space",
+        Keycode::Tab => "Tab",
+        Keycode::Enter => "Enter",
+        Keycode::Escape => "Escape",
+        Keycode::CapsLock => "CapsLock",
+        Keycode::Home => "Home",
+        Keycode::End => "End",
+        Keycode::PageUp => "PageUp",
+        Keycode::PageDown => "PageDown",
+        Keycode::Insert => "Insert",
+        Keycode::Delete => "Delete",
+
+        Keycode::F1 => "F1",
+        Keycode::F2 => "F2",
+        Keycode::F3 => "F3",
+        Keycode::F4 => "F4",
+        Keycode::F5 => "F5",
+        Keycode::F6 => "F6",
+        Keycode::F7 => "F7",
+        Keycode::F8 => "F8",
+        Keycode::F9 => "F9",
+        Keycode::F10 => "F10",
+        Keycode::F11 => "F11",
+        Keycode::F12 => "F12",
+        Keycode::F13 => "F13",
+        Keycode::F14 => "F14",
+        Keycode::F15 => "F15",
+        Keycode::F16 => "F16",
+        Keycode::F17 => "F17",
+        Keycode::F18 => "F18",
+        Keycode::F19 => "F19",
+        Keycode::F20 => "F20",
+
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// The reverse of `keycode_name`, used when loading a persisted config.
+pub fn keycode_from_name(name: &str) -> Option<Keycode> {
+    let keycode = match name {
+        "Key0" => Keycode::Key0,
+        "Key1" => Keycode::Key1,
+        "Key2" => Keycode::Key2,
+        "Key3" => Keycode::Key3,
+        "Key4" => Keycode::Key4,
+        "Key5" => Keycode::Key5,
+        "Key6" => Keycode::Key6,
+        "Key7" => Keycode::Key7,
+        "Key8" => Keycode::Key8,
+        "Key9" => Keycode::Key9,
+
+        "A" => Keycode::A,
+        "B" => Keycode::B,
+        "C" => Keycode::C,
+        "D" => Keycode::D,
+        "E" => Keycode::E,
+        "F" => Keycode::F,
+        "G" => Keycode::G,
+        "H" => Keycode::H,
+        "I" => Keycode::I,
+        "J" => Keycode::J,
+        "K" => Keycode::K,
+        "L" => Keycode::L,
+        "M" => Keycode::M,
+        "N" => Keycode::N,
+        "O" => Keycode::O,
+        "P" => Keycode::P,
+        "Q" => Keycode::Q,
+        "R" => Keycode::R,
+        "S" => Keycode::S,
+        "T" => Keycode::T,
+        "U" => Keycode::U,
+        "V" => Keycode::V,
+        "W" => Keycode::W,
+        "X" => Keycode::X,
+        "Y" => Keycode::Y,
+        "Z" => Keycode::Z,
+
+        "Up" => Keycode::Up,
+        "Down" => Keycode::Down,
+        "Left" => Keycode::Left,
+        "Right" => Keycode::Right,
+
+        "Numpad0" => Keycode::Numpad0,
+        "Numpad1" => Keycode::Numpad1,
+        "Numpad2" => Keycode::Numpad2,
+        "Numpad3" => Keycode::Numpad3,
+        "Numpad4" => Keycode::Numpad4,
+        "Numpad5" => Keycode::Numpad5,
+        "Numpad6" => Keycode::Numpad6,
+        "Numpad7" => Keycode::Numpad7,
+        "Numpad8" => Keycode::Numpad8,
+        "Numpad9" => Keycode::Numpad9,
+        "NumpadSubtract" => Keycode::NumpadSubtract,
+        "NumpadAdd" => Keycode::NumpadAdd,
+        "NumpadDivide" => Keycode::NumpadDivide,
+        "NumpadMultiply" => Keycode::NumpadMultiply,
+        "Grave" => Keycode::Grave,
+        "Minus" => Keycode::Minus,
+        "Equal" => Keycode::Equal,
+        "LeftBracket" => Keycode::LeftBracket,
+        "RightBracket" => Keycode::RightBracket,
+        "BackSlash" => Keycode::BackSlash,
+        "Semicolon" => Keycode::Semicolon,
+        "Apostrophe" => Keycode::Apostrophe,
+        "Comma" => Keycode::Comma,
+        "Dot" => Keycode::Dot,
+        "Slash" => Keycode::Slash,
+
+        "Space" => Keycode::Space,
+        "Tab" => Keycode::Tab,
+        "Enter" => Keycode::Enter,
+        "Escape" => Keycode::Escape,
+        "CapsLock" => Keycode::CapsLock,
+        "Home" => Keycode::Home,
+        "End" => Keycode::End,
+        "PageUp" => Keycode::PageUp,
+        "PageDown" => Keycode::PageDown,
+        "Insert" => Keycode::Insert,
+        "Delete" => Keycode::Delete,
+
+        "F1" => Keycode::F1,
+        "F2" => Keycode::F2,
+        "F3" => Keycode::F3,
+        "F4" => Keycode::F4,
+        "F5" => Keycode::F5,
+        "F6" => Keycode::F6,
+        "F7" => Keycode::F7,
+        "F8" => Keycode::F8,
+        "F9" => Keycode::F9,
+        "F10" => Keycode::F10,
+        "F11" => Keycode::F11,
+        "F12" => Keycode::F12,
+        "F13" => Keycode::F13,
+        "F14" => Keycode::F14,
+        "F15" => Keycode::F15,
+        "F16" => Keycode::F16,
+        "F17" => Keycode::F17,
+        "F18" => Keycode::F18,
+        "F19" => Keycode::F19,
+        "F20" => Keycode::F20,
+
+        _ => return None,
+    };
+    Some(keycode)
+}
+
+fn keycode_to_char(keycode: &Keycode) -> Option<char> {
+    match keycode {
+        Keycode::Key0 => Some('0'),
+        Keycode::Key1 => Some('1'),
+        Keycode::Key2 => Some('2'),
+        Keycode::Key3 => Some('3'),
+        Keycode::Key4 => Some('4'),
+        Keycode::Key5 => Some('5'),
+        Keycode::Key6 => Some('6'),
+        Keycode::Key7 => Some('7'),
+        Keycode::Key8 => Some('8'),
+        Keycode::Key9 => Some('9'),
+
+        Keycode::A => Some('A'),
+        Keycode::B => Some('B'),
+        Keycode::C => Some('C'),
+        Keycode::D => Some('D'),
+        Keycode::E => Some('E'),
+        Keycode::F => Some('F'),
+        Keycode::G => Some('G'),
+        Keycode::H => Some('H'),
+        Keycode::I => Some('I'),
+        Keycode::J => Some('J'),
+        Keycode::K => Some('K'),
+        Keycode::L => Some('L'),
+        Keycode::M => Some('M'),
+        Keycode::N => Some('N'),
+        Keycode::O => Some('O'),
+        Keycode::P => Some('P'),
+        Keycode::Q => Some('Q'),
+        Keycode::R => Some('R'),
+        Keycode::S => Some('S'),
+        Keycode::T => Some('T'),
+        Keycode::U => Some('U'),
+        Keycode::V => Some('V'),
+        Keycode::W => Some('W'),
+        Keycode::X => Some('X'),
+        Keycode::Y => Some('Y'),
+        Keycode::Z => Some('Z'),
+
+        Keycode::Up => Some('^'),
+        Keycode::Down => Some('^'),
+        Keycode::Left => Some('^'),
+        Keycode::Right => Some('^'),
+
+        Keycode::Numpad0 => Some('0'),
+        Keycode::Numpad1 => Some('1'),
+        Keycode::Numpad2 => Some('2'),
+        Keycode::Numpad3 => Some('3'),
+        Keycode::Numpad4 => Some('4'),
+        Keycode::Numpad5 => Some('5'),
+        Keycode::Numpad6 => Some('6'),
+        Keycode::Numpad7 => Some('7'),
+        Keycode::Numpad8 => Some('8'),
+        Keycode::Numpad9 => Some('9'),
+        Keycode::NumpadSubtract => Some('-'),
+        Keycode::NumpadAdd => Some('+'),
+        Keycode::NumpadDivide => Some('/'),
+        Keycode::NumpadMultiply => Some('*'),
+        Keycode::Grave => Some('`'),
+        Keycode::Minus => Some('-'),
+        Keycode::Equal => Some('='),
+        Keycode::LeftBracket => Some('['),
+        Keycode::RightBracket => Some(']'),
+        Keycode::BackSlash => Some('\\'),
+        Keycode::Semicolon => Some(':'),
+        Keycode::Apostrophe => Some('\''),
+        Keycode::Comma => Some(','),
+        Keycode::Dot => Some('.'),
+        Keycode::Slash => Some('/'),
+
+        _ => None,
+    }
+}
@@ -1,3 +1,10 @@
+mod audio;
+mod config;
+mod input;
+mod layout;
+
+use audio::Metronome;
+use config::{AppConfig, V6Prac};
 use device_query::{DeviceQuery, DeviceState, Keycode};
 use femtovg::{renderer::OpenGl, Canvas, Color, ErrorKind, Paint, Path, TextMetrics};
 use glutin::config::ConfigTemplateBuilder;
@@ -5,8 +12,10 @@ use glutin::context::ContextAttributesBuilder;
 use glutin::context::PossiblyCurrentContext;
 use glutin::display::GetGlDisplay;
 use glutin::prelude::{GlDisplay, NotCurrentGlContextSurfaceAccessor};
-use glutin::surface::{GlSurface, Surface, SurfaceAttributesBuilder, WindowSurface};
+use glutin::surface::{GlSurface, Surface, SurfaceAttributesBuilder, SwapInterval, WindowSurface};
 use glutin_winit::DisplayBuilder;
+use input::{InputBinding, InputSource};
+use layout::{binding_to_display, build_layouts, Action, ActionKind, DisplayChar, Layout};
 use raw_window_handle::*;
 use std::num::NonZeroU32;
 use std::{collections::VecDeque, time::Instant};
@@ -15,69 +24,161 @@ use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Window, WindowBuilder};
 
 fn main() {
-    let keys_to_track = customize();
+    // Passing `--recustomize` skips any saved config and re-binds everything
+    // from scratch, overwriting it once done.
+    let force_recustomize = std::env::args().any(|a| a == "--recustomize");
 
-    init_and_run(keys_to_track);
+    let app = if force_recustomize {
+        V6Prac::recustomize()
+    } else {
+        V6Prac::load_or_customize()
+    };
+
+    app.run();
 }
 
-fn customize() -> Vec<Keycode> {
-    let device_state = DeviceState::new();
-    // Wait for enter (or any other key) to be released
+fn customize() -> Vec<InputBinding> {
+    let mut sources = input::default_sources();
+
+    // Wait for every source to go quiet before we start listening for binds
     loop {
-        let poll = device_state.get_keys();
-        if poll.is_empty() {
+        let anything_pressed = sources.iter_mut().any(|s| !s.poll_pressed().is_empty());
+        if !anything_pressed {
             break;
         }
     }
 
-    println!("Please press all the keys you would like to track, then press Backspace to end customization. If you want to track backspace inputs, tough luck.");
-    let mut keys = Vec::new();
+    println!("Please press all the keys/buttons you would like to track, then press Backspace to end customization. If you want to track backspace inputs, tough luck.");
+    let mut bindings = Vec::new();
     let mut done = false;
-    let mut fail_keys = Vec::new();
+    let mut fail_bindings = Vec::new();
 
     while !done {
-        let poll = device_state.get_keys();
-        for k in poll {
-            if k == Keycode::Backspace {
-                done = true;
-            } else if !keys.contains(&k) {
-                if keycode_to_char(&k).is_none() {
-                    if !fail_keys.contains(&k) {
-                        println!("{:?} not supported (yet). Sorry!", k);
-                        fail_keys.push(k)
+        for source in sources.iter_mut() {
+            for binding in source.poll_pressed() {
+                if binding == InputBinding::Key(Keycode::Backspace) {
+                    done = true;
+                } else if !bindings.contains(&binding) {
+                    if binding_to_display(&binding).is_none() {
+                        if !fail_bindings.contains(&binding) {
+                            println!("{:?} not supported (yet). Sorry!", binding);
+                            fail_bindings.push(binding)
+                        }
+                    } else {
+                        println!("Tracking {:?}", binding);
+                        bindings.push(binding);
                     }
-                } else {
-                    println!("Tracking {:?}", k);
-                    keys.push(k);
                 }
             }
         }
     }
-    println!("{:?} keys recorded.", keys.len());
+    println!("{:?} bindings recorded.", bindings.len());
 
-    keys
+    bindings
 }
 
 // How many past polls to display
 const MAX_QUEUE_SIZE: usize = 20;
-// 1 frame in VVVVVV is 34ms, you will want to adapt this for other games
-const NANOS_PER_FRAME: u128 = 34000000;
-// How many polls should happen per in-game frame
-// Increasing this will make the program more CPU intensive (when limiting fps)
-const MAX_POLLS_PER_FRAME: u128 = 20;
+
+// Every hotkey below only fires while this is also held, so binding any of
+// Tab/P/Space/M/V as a tracked action (all fully trackable keys since the
+// glyph-label expansion) can't be mistaken for one: a tracked press of plain
+// `Tab` doesn't touch `check_hotkeys` at all, only `LControl`+`Tab` does.
+// `LAlt` was the first choice, but `LAlt`+`Tab` is the OS task-switcher on
+// virtually every platform, so holding it to switch layouts also hands focus
+// away to the desktop. `LControl` isn't claimed at that level.
+const HOTKEY_MODIFIER: Keycode = Keycode::LControl;
+
+// Hotkey that cycles to the next loaded layout
+const LAYOUT_SWITCH_KEY: Keycode = Keycode::Tab;
+// Hotkey that freezes/unfreezes the scrolling history
+const PAUSE_KEY: Keycode = Keycode::P;
+// While paused, advances the history by exactly one recorded poll
+const STEP_KEY: Keycode = Keycode::Space;
+// Hotkey that toggles the frame-tick metronome, when audio is available
+const METRONOME_KEY: Keycode = Keycode::M;
+// Hotkey that cycles the swap-buffer interval between vsync and immediate
+const VSYNC_TOGGLE_KEY: Keycode = Keycode::V;
+
+/// Governs how `surface.swap_buffers` paces redraws. Polling is no longer
+/// throttled to match this (see `maybe_poll`); it only controls how `render`
+/// is capped, decoupling the two the way `draw_past_inputs`'s frame-timing
+/// math wants.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SwapIntervalMode {
+    Vsync,
+    Immediate,
+}
+
+impl SwapIntervalMode {
+    fn from_vsync_flag(vsync: bool) -> Self {
+        if vsync {
+            SwapIntervalMode::Vsync
+        } else {
+            SwapIntervalMode::Immediate
+        }
+    }
+
+    fn swap_interval(self) -> SwapInterval {
+        match self {
+            SwapIntervalMode::Vsync => SwapInterval::Wait(NonZeroU32::new(1).unwrap()),
+            SwapIntervalMode::Immediate => SwapInterval::DontWait,
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            SwapIntervalMode::Vsync => SwapIntervalMode::Immediate,
+            SwapIntervalMode::Immediate => SwapIntervalMode::Vsync,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SwapIntervalMode::Vsync => "vsync",
+            SwapIntervalMode::Immediate => "immediate",
+        }
+    }
+}
 
 struct GlobalState {
-    actions: Vec<DisplayableAction>,
+    layouts: Vec<Layout>,
+    current_layout: usize,
 
-    limit_fps: bool,
+    // 1 frame in VVVVVV is 34ms; configurable per-game via `AppConfig`
+    nanos_per_frame: u128,
+
+    swap_interval: SwapIntervalMode,
     last_fps: f64,
     last_dt: u128,
 
+    // While paused, `maybe_poll` stops pushing new `RecordedPoll`s so the
+    // history can be examined input-by-input. `step_once` advances it by
+    // exactly one poll before pausing again.
+    paused: bool,
+    step_once: bool,
+
     last_poll_attempt: Instant,
     attempts_since_last_poll: usize,
     poll_queue: VecDeque<RecordedPoll>,
 
-    device_state: DeviceState,
+    input_sources: Vec<Box<dyn InputSource>>,
+
+    // `None` if no output device was available; the metronome hotkey is a
+    // no-op in that case.
+    metronome: Option<Metronome>,
+    // How long each currently-active action has been held, and the last
+    // integer frame count we played the "exact frame" cue for, so we only
+    // fire once per newly-crossed frame boundary rather than every poll.
+    active_since: Vec<Option<Instant>>,
+    last_exact_frame_played: Vec<u128>,
+
+    // Dedicated listener for app-level hotkeys (layout switching, etc.), kept
+    // separate from `input_sources`. Collision with a tracked action is ruled
+    // out by `HOTKEY_MODIFIER`, not by separation alone: a raw key like `Tab`
+    // can be tracked and still mean nothing to `check_hotkeys` on its own.
+    hotkey_state: DeviceState,
+    hotkeys_held: Vec<Keycode>,
 
     canvas: Canvas<OpenGl>,
     window: Window,
@@ -85,47 +186,62 @@ struct GlobalState {
     surface: Surface<WindowSurface>,
 }
 
-struct DisplayableAction {
-    key: Keycode,
-    display_char: DisplayChar,
-}
-
-enum DisplayChar {
-    Simple(char),
-    Rotated(f32, char),
-}
+/// Per-action state for a single poll: `0` means inactive, for a `Button`
+/// action `1` means held, and for an `Axis` action `-1`/`1` reflect which
+/// side is held.
+type ActionState = i8;
 
 struct RecordedPoll {
     timestamp: Instant,
-    keys: Vec<bool>,
+    keys: Vec<ActionState>,
     dt_before: u128,
     dt_after: u128,
+    // Kernel-reported press instant per action, when the active input
+    // source can provide one (currently only `EvdevSource`). `None` means
+    // we only know this key was down sometime in `dt_before`/`dt_after`.
+    precise_press: Vec<Option<Instant>>,
 }
 
-pub fn init_and_run(keys_to_track: Vec<Keycode>) {
-    let w = 300 + keys_to_track.len() as u32 * 50;
-    let h = 800;
+pub fn init_and_run_with_config(config: AppConfig, title: String) {
+    let layouts = build_layouts(config.bindings, &config.extra_layouts);
+    let (w, h) = window_size_for(&layouts[0]);
 
     let event_loop = EventLoop::new();
-    let (canvas, window, context, surface) = create_windowed_context(&event_loop, w, h);
-    let device_state = DeviceState::new();
+    let (canvas, window, context, surface) = create_windowed_context(&event_loop, &title, w, h);
+
+    let input_sources = input::default_sources();
 
     let poll_queue = VecDeque::with_capacity(MAX_QUEUE_SIZE);
 
-    let actions = create_actions(keys_to_track);
+    let action_count = layouts[0].actions.len();
 
     let mut state = GlobalState {
-        actions,
+        layouts,
+        current_layout: 0,
 
-        limit_fps: false,
+        // Widened from `config.nanos_per_frame`'s `u64` (the most `toml` can
+        // serialize) to match `frames_held`'s `u128` elsewhere in this struct.
+        nanos_per_frame: config.nanos_per_frame as u128,
+
+        swap_interval: SwapIntervalMode::from_vsync_flag(config.vsync),
         last_fps: 0.0,
         last_dt: 0,
 
+        paused: false,
+        step_once: false,
+
         last_poll_attempt: Instant::now(),
         attempts_since_last_poll: 0,
         poll_queue,
 
-        device_state,
+        input_sources,
+
+        metronome: Metronome::new(),
+        active_since: vec![None; action_count],
+        last_exact_frame_played: vec![0; action_count],
+
+        hotkey_state: DeviceState::new(),
+        hotkeys_held: Vec::new(),
 
         canvas,
         window,
@@ -133,11 +249,22 @@ pub fn init_and_run(keys_to_track: Vec<Keycode>) {
         surface,
     };
 
+    if let Err(e) = state
+        .surface
+        .set_swap_interval(&state.context, state.swap_interval.swap_interval())
+    {
+        println!("Couldn't set initial swap interval: {e}");
+    }
+
     event_loop.run(move |event, _, control_flow| {
         event_handler(event, control_flow, &mut state);
     });
 }
 
+fn window_size_for(layout: &Layout) -> (u32, u32) {
+    (300 + layout.actions.len() as u32 * 50, 800)
+}
+
 fn event_handler(event: Event<()>, control_flow: &mut ControlFlow, state: &mut GlobalState) {
     *control_flow = ControlFlow::Poll;
 
@@ -145,7 +272,10 @@ fn event_handler(event: Event<()>, control_flow: &mut ControlFlow, state: &mut G
         // Last event to be emitted, do any necessary cleanup here
         Event::LoopDestroyed => println!("Goodbye!"),
         // This is polled whenever no events are in queue
-        Event::MainEventsCleared => maybe_poll(state),
+        Event::MainEventsCleared => {
+            check_hotkeys(state);
+            maybe_poll(state);
+        }
         // Window isn't resizable, so we only need to deal with CloseRequested
         Event::WindowEvent {
             window_id: _,
@@ -157,39 +287,157 @@ fn event_handler(event: Event<()>, control_flow: &mut ControlFlow, state: &mut G
     };
 }
 
+fn check_hotkeys(state: &mut GlobalState) {
+    let held = state.hotkey_state.get_keys();
+    let previously_held = std::mem::replace(&mut state.hotkeys_held, held.clone());
+    let modifier_held = held.contains(&HOTKEY_MODIFIER);
+    let just_pressed =
+        |key: Keycode| modifier_held && held.contains(&key) && !previously_held.contains(&key);
+
+    if just_pressed(LAYOUT_SWITCH_KEY) {
+        switch_layout(state);
+    }
+    if just_pressed(PAUSE_KEY) {
+        state.paused = !state.paused;
+        if state.paused {
+            // Otherwise the first step's `last_dt` spans the entire pause
+            // (from the last poll before pausing to whenever `STEP_KEY` is
+            // next pressed), not the one real poll gap the step represents.
+            state.last_poll_attempt = Instant::now();
+        }
+        state.window.request_redraw();
+    }
+    if just_pressed(STEP_KEY) && state.paused {
+        state.step_once = true;
+    }
+    if just_pressed(METRONOME_KEY) {
+        if let Some(metronome) = &state.metronome {
+            metronome.toggle();
+        }
+    }
+    if just_pressed(VSYNC_TOGGLE_KEY) {
+        toggle_swap_interval(state);
+    }
+}
+
+fn toggle_swap_interval(state: &mut GlobalState) {
+    state.swap_interval = state.swap_interval.toggled();
+    if let Err(e) = state
+        .surface
+        .set_swap_interval(&state.context, state.swap_interval.swap_interval())
+    {
+        println!("Couldn't set swap interval: {e}");
+    }
+    state.window.request_redraw();
+}
+
+fn switch_layout(state: &mut GlobalState) {
+    state.current_layout = (state.current_layout + 1) % state.layouts.len();
+    state.poll_queue.clear();
+
+    let action_count = state.layouts[state.current_layout].actions.len();
+    state.active_since = vec![None; action_count];
+    state.last_exact_frame_played = vec![0; action_count];
+
+    let layout = &state.layouts[state.current_layout];
+    let (w, h) = window_size_for(layout);
+    let action_names: Vec<&str> = layout.actions.iter().map(|a| a.name.as_str()).collect();
+    println!("Switched to layout {:?}: {:?}", layout.name, action_names);
+
+    state.window.set_inner_size(winit::dpi::PhysicalSize::new(w, h));
+    if let (Some(width), Some(height)) = (NonZeroU32::new(w), NonZeroU32::new(h)) {
+        state.surface.resize(&state.context, width, height);
+    }
+
+    state.window.request_redraw();
+}
+
 fn maybe_poll(state: &mut GlobalState) {
     let now = Instant::now();
-    let dt = now.duration_since(state.last_poll_attempt);
 
-    // Limit polling rate if needed
-    let enough_time_passed = if state.limit_fps {
-        NANOS_PER_FRAME <= dt.as_nanos().checked_mul(MAX_POLLS_PER_FRAME).unwrap()
-    } else {
-        true
-    };
+    // Scheduled off this same `Instant` clock rather than the render loop,
+    // so the click lands on the frame boundary regardless of how often we
+    // actually redraw.
+    if !state.paused {
+        let nanos_per_frame = state.nanos_per_frame;
+        if let Some(metronome) = state.metronome.as_mut() {
+            metronome.maybe_tick_frame(now, nanos_per_frame);
+        }
+    }
 
-    if enough_time_passed {
-        state.attempts_since_last_poll += 1;
-        state.last_dt = dt.as_nanos();
+    if state.paused {
+        if state.step_once {
+            state.step_once = false;
+            state.attempts_since_last_poll += 1;
+            state.last_dt = now.duration_since(state.last_poll_attempt).as_nanos();
 
-        poll(&now, state);
+            poll(&now, state);
 
-        state.last_poll_attempt = now;
+            state.last_poll_attempt = now;
+        }
+        return;
     }
+
+    // Polling itself is never throttled: render cadence is governed by
+    // `swap_interval` instead, so polling can run flat out, which is what
+    // `draw_past_inputs`'s precise frame-timing math wants.
+    let dt = now.duration_since(state.last_poll_attempt);
+    state.attempts_since_last_poll += 1;
+    state.last_dt = dt.as_nanos();
+
+    poll(&now, state);
+
+    state.last_poll_attempt = now;
 }
 
 fn poll(now: &Instant, state: &mut GlobalState) {
-    let last_poll = state.poll_queue.front();
-    let poll = get_pressed_keys(&state.actions, now, &state.device_state);
+    // Borrowing just this field (rather than all of `*state`) leaves
+    // `state.input_sources` free for the `&mut` borrow right below.
+    let actions = &state.layouts[state.current_layout].actions;
+    let poll = get_pressed_keys(actions, now, &mut state.input_sources);
+
+    // Decided before `track_exact_frame_cues` takes `&mut state` below, so
+    // this doesn't hold `state.poll_queue.front()`'s borrow across that call.
+    let should_record = match state.poll_queue.front() {
+        Some(last_poll) => inputs_changed(&poll, last_poll),
+        None => true,
+    };
+
+    track_exact_frame_cues(now, &poll, state);
 
-    // Record poll if it's the first one, or if the pressed keys have changed
-    if last_poll.is_none() || inputs_changed(&poll, last_poll.unwrap()) {
+    if should_record {
         record_poll(now, poll, state);
 
         state.window.request_redraw();
     }
 }
 
+/// Plays the metronome's distinct tone exactly once per tracked input whose
+/// held duration crosses a new integer frame count, e.g. going from held
+/// 1.98 frames to held 2.01 frames plays the cue for "2".
+fn track_exact_frame_cues(now: &Instant, poll: &RecordedPoll, state: &mut GlobalState) {
+    if state.metronome.is_none() {
+        return;
+    }
+
+    for idx in 0..poll.keys.len() {
+        if poll.keys[idx] == 0 {
+            state.active_since[idx] = None;
+            continue;
+        }
+
+        let since = *state.active_since[idx].get_or_insert(*now);
+        let frames_held = now.duration_since(since).as_nanos() / state.nanos_per_frame;
+
+        if frames_held > 0 && frames_held != state.last_exact_frame_played[idx] {
+            state.last_exact_frame_played[idx] = frames_held;
+            if let Some(metronome) = &state.metronome {
+                metronome.tick_exact_frame();
+            }
+        }
+    }
+}
+
 fn record_poll(now: &Instant, mut poll: RecordedPoll, state: &mut GlobalState) {
     state.last_fps = if let Some(last_poll) = state.poll_queue.front() {
         let delta = now.duration_since(last_poll.timestamp);
@@ -212,16 +460,48 @@ fn record_poll(now: &Instant, mut poll: RecordedPoll, state: &mut GlobalState) {
 }
 
 fn get_pressed_keys(
-    actions: &Vec<DisplayableAction>,
+    actions: &[Action],
     now: &Instant,
-    device_state: &DeviceState,
+    input_sources: &mut Vec<Box<dyn InputSource>>,
 ) -> RecordedPoll {
-    let poll = device_state.get_keys();
+    let mut pressed = Vec::new();
+    for source in input_sources.iter_mut() {
+        pressed.extend(source.poll_pressed());
+    }
 
-    let mut keys = vec![false; actions.len()];
-    for (idx, a) in actions.iter().enumerate() {
-        if poll.contains(&a.key) {
-            keys[idx] = true;
+    let mut keys = vec![0; actions.len()];
+    let mut precise_press = vec![None; actions.len()];
+    for (idx, action) in actions.iter().enumerate() {
+        match &action.kind {
+            ActionKind::Button { binding, .. } => {
+                if pressed.contains(binding) {
+                    keys[idx] = 1;
+                    precise_press[idx] = input_sources
+                        .iter()
+                        .find_map(|source| source.precise_event_instant(binding));
+                }
+            }
+            ActionKind::Axis {
+                negative, positive, ..
+            } => {
+                let (held_negative, held_positive) =
+                    (pressed.contains(negative), pressed.contains(positive));
+                keys[idx] = match (held_negative, held_positive) {
+                    (true, false) => -1,
+                    (false, true) => 1,
+                    _ => 0,
+                };
+                let held_binding = match keys[idx] {
+                    -1 => Some(negative),
+                    1 => Some(positive),
+                    _ => None,
+                };
+                if let Some(binding) = held_binding {
+                    precise_press[idx] = input_sources
+                        .iter()
+                        .find_map(|source| source.precise_event_instant(binding));
+                }
+            }
         }
     }
 
@@ -230,6 +510,7 @@ fn get_pressed_keys(
         keys,
         dt_before: u128::MAX,
         dt_after: u128::MAX,
+        precise_press,
     }
 }
 
@@ -250,8 +531,8 @@ fn render(state: &mut GlobalState) {
     state.canvas.clear_rect(
         0,
         0,
-        state.canvas.width() as u32,
-        state.canvas.height() as u32,
+        state.canvas.width(),
+        state.canvas.height(),
         bg_color,
     );
 
@@ -259,19 +540,28 @@ fn render(state: &mut GlobalState) {
         &mut state.canvas,
         &state.last_fps,
         state.last_dt,
+        state.nanos_per_frame,
+        state.swap_interval.label(),
         &active_paint,
     );
+    if state.paused {
+        draw_pause_indicator(&mut state.canvas, &active_paint);
+    }
+    // Borrowing just this field leaves `state.canvas`, already borrowed
+    // mutably above, out of it.
     draw_current_inputs(
         &mut state.canvas,
-        &state.actions,
+        &state.layouts[state.current_layout].actions,
         state.poll_queue.front(),
         &active_paint,
         &inactive_paint,
     );
     draw_past_inputs(
         &mut state.canvas,
-        &state.actions,
+        &state.layouts[state.current_layout].actions,
         &state.poll_queue,
+        state.nanos_per_frame,
+        state.paused,
         &active_paint,
     );
 
@@ -280,46 +570,89 @@ fn render(state: &mut GlobalState) {
     state.surface.swap_buffers(&state.context).unwrap();
 }
 
-fn draw_fps_counter(canvas: &mut Canvas<OpenGl>, fps: &f64, dt: u128, paint: &Paint) {
-    let frame_dt = dt as f64 / NANOS_PER_FRAME as f64;
+fn draw_fps_counter(
+    canvas: &mut Canvas<OpenGl>,
+    fps: &f64,
+    dt: u128,
+    nanos_per_frame: u128,
+    swap_interval_label: &str,
+    paint: &Paint,
+) {
+    let frame_dt = dt as f64 / nanos_per_frame as f64;
 
     let _ = canvas.fill_text(
         10.0,
         23.0,
-        format!("{: >4} fps +/- {: >2.2}f", fps, frame_dt),
+        format!(
+            "{: >4} fps +/- {: >2.2}f ({})",
+            fps, frame_dt, swap_interval_label
+        ),
         paint,
     );
 }
 
+fn draw_pause_indicator(canvas: &mut Canvas<OpenGl>, paint: &Paint) {
+    let _ = canvas.fill_text(10.0, 42.0, "PAUSED (space to step)", paint);
+}
+
+/// Resolves an action + its current state to the glyph that should be drawn
+/// for it, if any. `None` means nothing lights up for this state (e.g. a
+/// button that isn't held, or an axis sitting at `0`).
+fn active_display_char(action: &Action, state: ActionState) -> Option<&DisplayChar> {
+    match &action.kind {
+        ActionKind::Button { display, .. } if state != 0 => Some(display),
+        ActionKind::Axis {
+            negative_display, ..
+        } if state < 0 => Some(negative_display),
+        ActionKind::Axis {
+            positive_display, ..
+        } if state > 0 => Some(positive_display),
+        _ => None,
+    }
+}
+
+/// The glyph always drawn as a dim baseline for an action, regardless of
+/// whether it's currently active. Axes use their positive-side glyph as the
+/// neutral placeholder.
+fn inactive_display_char(action: &Action) -> &DisplayChar {
+    match &action.kind {
+        ActionKind::Button { display, .. } => display,
+        ActionKind::Axis {
+            positive_display, ..
+        } => positive_display,
+    }
+}
+
+fn draw_glyph(canvas: &mut Canvas<OpenGl>, display: &DisplayChar, x: f32, y: f32, paint: &Paint) {
+    let _ = match *display {
+        DisplayChar::Simple(c) => draw_char_at_pos(canvas, c, x, y, 0.0, paint),
+        DisplayChar::Rotated(angle, c) => draw_char_at_pos(canvas, c, x, y, angle, paint),
+        DisplayChar::Label(label) => draw_text_at_pos(canvas, label, x, y, paint),
+    };
+}
+
 // TODO: fix magic numbers etc.
 fn draw_current_inputs(
     canvas: &mut Canvas<OpenGl>,
-    actions: &[DisplayableAction],
+    actions: &[Action],
     maybe_poll: Option<&RecordedPoll>,
     active_paint: &Paint,
     inactive_paint: &Paint,
 ) {
     let left_margin = 22.5;
-    let text_y_pos = canvas.height() / 2.0 - 22.5;
-    let separator_y = canvas.height() / 2.0 - 35.0;
+    let text_y_pos = canvas.height() as f32 / 2.0 - 22.5;
+    let separator_y = canvas.height() as f32 / 2.0 - 35.0;
 
     // Separator line
     let mut path = Path::new();
     path.move_to(0.0, separator_y);
-    path.line_to(canvas.width() / 2.0, separator_y);
+    path.line_to(canvas.width() as f32 / 2.0, separator_y);
     canvas.stroke_path(&path, active_paint);
 
     // Inactive keys
     let mut x = left_margin;
     for action in actions.iter() {
-        let _ = match action.display_char {
-            DisplayChar::Simple(c) => {
-                draw_char_at_pos(canvas, c, x, text_y_pos, 0.0, inactive_paint)
-            }
-            DisplayChar::Rotated(angle, c) => {
-                draw_char_at_pos(canvas, c, x, text_y_pos, angle, inactive_paint)
-            }
-        };
+        draw_glyph(canvas, inactive_display_char(action), x, text_y_pos, inactive_paint);
         x += 25.0;
     }
 
@@ -327,16 +660,8 @@ fn draw_current_inputs(
         // Active keys
         let mut x = left_margin;
         for (idx, &k) in poll.keys.iter().enumerate() {
-            if k {
-                let action = &actions[idx];
-                let _ = match action.display_char {
-                    DisplayChar::Simple(c) => {
-                        draw_char_at_pos(canvas, c, x, text_y_pos, 0.0, active_paint)
-                    }
-                    DisplayChar::Rotated(angle, c) => {
-                        draw_char_at_pos(canvas, c, x, text_y_pos, angle, active_paint)
-                    }
-                };
+            if let Some(display) = active_display_char(&actions[idx], k) {
+                draw_glyph(canvas, display, x, text_y_pos, active_paint);
             }
             x += 25.0;
         }
@@ -345,8 +670,10 @@ fn draw_current_inputs(
 
 fn draw_past_inputs(
     canvas: &mut Canvas<OpenGl>,
-    actions: &[DisplayableAction],
+    actions: &[Action],
     polls: &VecDeque<RecordedPoll>,
+    nanos_per_frame: u128,
+    paused: bool,
     paint: &Paint,
 ) {
     // No past inputs to render
@@ -355,7 +682,7 @@ fn draw_past_inputs(
     }
 
     let left_margin = 22.5;
-    let mut y = canvas.height() / 2.0 - 52.5;
+    let mut y = canvas.height() as f32 / 2.0 - 52.5;
 
     let mut iter = polls.iter();
     let mut next_poll = iter.next().unwrap();
@@ -364,24 +691,32 @@ fn draw_past_inputs(
         // polled inputs could have started up to `dt_before` nanos earlier, ended up to `dt_after` nanos earlier
         // To get the expected duration of the input, we can take `dt - dt_after/2 + dt_before/2`
         // Then we still have an uncertainty of +/- (dt_after + dt_before)/2
+        //
+        // If a precise backend (e.g. evdev) reported the exact kernel
+        // timestamp this poll's keys went down, use that as the start
+        // instead of the `dt_before/2` estimate, cutting the uncertainty
+        // window in half.
         let dt = next_poll.timestamp.duration_since(poll.timestamp);
+        let precise_start = poll.precise_press.iter().flatten().next();
+
+        let (min_nanos_held, epsilon_nanos) = match precise_start {
+            Some(start) => (
+                poll.timestamp.duration_since(*start).as_nanos() + dt.as_nanos() - poll.dt_after,
+                poll.dt_after / 2,
+            ),
+            None => (
+                dt.as_nanos() - poll.dt_after,
+                (poll.dt_after + poll.dt_before) / 2,
+            ),
+        };
 
-        let min_nanos_held = dt.as_nanos() - poll.dt_after;
-        let epsilon_nanos = (poll.dt_after + poll.dt_before) / 2;
-
-        let frames_held = (min_nanos_held + epsilon_nanos) as f64 / NANOS_PER_FRAME as f64;
-        let _uncertainty = epsilon_nanos as f64 / NANOS_PER_FRAME as f64;
+        let frames_held = (min_nanos_held + epsilon_nanos) as f64 / nanos_per_frame as f64;
+        let _uncertainty = epsilon_nanos as f64 / nanos_per_frame as f64;
 
         let mut x = left_margin;
         for (idx, &k) in poll.keys.iter().enumerate() {
-            if k {
-                let action = &actions[idx];
-                let _ = match action.display_char {
-                    DisplayChar::Simple(c) => draw_char_at_pos(canvas, c, x, y, 0.0, paint),
-                    DisplayChar::Rotated(angle, c) => {
-                        draw_char_at_pos(canvas, c, x, y, angle, paint)
-                    }
-                };
+            if let Some(display) = active_display_char(&actions[idx], k) {
+                draw_glyph(canvas, display, x, y, paint);
             }
             x += 25.0;
         }
@@ -391,8 +726,10 @@ fn draw_past_inputs(
         next_poll = poll;
         y -= 20.0;
 
-        // Stop rendering if too many polls in queue
-        if y <= 40.0 {
+        // Stop rendering once we run out of vertical space, unless paused —
+        // while paused there's no new history pushing old polls out, so
+        // let the examined range scroll further back than normally fits.
+        if y <= 40.0 && !paused {
             break;
         }
     }
@@ -461,6 +798,7 @@ where
 
 fn create_windowed_context<T>(
     event_loop: &EventLoop<T>,
+    title: &str,
     w: u32,
     h: u32,
 ) -> (
@@ -471,7 +809,7 @@ fn create_windowed_context<T>(
 ) {
     let window_size = winit::dpi::PhysicalSize::new(w, h);
     let window_builder = WindowBuilder::new()
-        .with_title("v6prac")
+        .with_title(title)
         .with_inner_size(window_size)
         .with_resizable(false);
 
@@ -539,105 +877,3 @@ fn create_windowed_context<T>(
 
     (canvas, window, gl_context, surface)
 }
-
-fn create_actions(keys_to_track: Vec<Keycode>) -> Vec<DisplayableAction> {
-    let mut actions = Vec::with_capacity(keys_to_track.len());
-    for k in keys_to_track.iter() {
-        let angle = match k {
-            Keycode::Up => 0.0,
-            Keycode::Down => std::f32::consts::PI,
-            Keycode::Left => -std::f32::consts::FRAC_PI_2,
-            Keycode::Right => std::f32::consts::FRAC_PI_2,
-            _ => 0.0,
-        };
-        let c = keycode_to_char(k).unwrap();
-
-        let display_char = if angle != 0.0 {
-            DisplayChar::Rotated(angle, c)
-        } else {
-            DisplayChar::Simple(c)
-        };
-
-        actions.push(DisplayableAction {
-            key: *k,
-            display_char,
-        });
-    }
-
-    actions
-}
-
-fn keycode_to_char(keycode: &Keycode) -> Option<char> {
-    match keycode {
-        Keycode::Key0 => Some('0'),
-        Keycode::Key1 => Some('1'),
-        Keycode::Key2 => Some('2'),
-        Keycode::Key3 => Some('3'),
-        Keycode::Key4 => Some('4'),
-        Keycode::Key5 => Some('5'),
-        Keycode::Key6 => Some('6'),
-        Keycode::Key7 => Some('7'),
-        Keycode::Key8 => Some('8'),
-        Keycode::Key9 => Some('9'),
-
-        Keycode::A => Some('A'),
-        Keycode::B => Some('B'),
-        Keycode::C => Some('C'),
-        Keycode::D => Some('D'),
-        Keycode::E => Some('E'),
-        Keycode::F => Some('F'),
-        Keycode::G => Some('G'),
-        Keycode::H => Some('H'),
-        Keycode::I => Some('I'),
-        Keycode::J => Some('J'),
-        Keycode::K => Some('K'),
-        Keycode::L => Some('L'),
-        Keycode::M => Some('M'),
-        Keycode::N => Some('N'),
-        Keycode::O => Some('O'),
-        Keycode::P => Some('P'),
-        Keycode::Q => Some('Q'),
-        Keycode::R => Some('R'),
-        Keycode::S => Some('S'),
-        Keycode::T => Some('T'),
-        Keycode::U => Some('U'),
-        Keycode::V => Some('V'),
-        Keycode::W => Some('W'),
-        Keycode::X => Some('X'),
-        Keycode::Y => Some('Y'),
-        Keycode::Z => Some('Z'),
-
-        Keycode::Up => Some('^'),
-        Keycode::Down => Some('^'),
-        Keycode::Left => Some('^'),
-        Keycode::Right => Some('^'),
-
-        Keycode::Numpad0 => Some('0'),
-        Keycode::Numpad1 => Some('1'),
-        Keycode::Numpad2 => Some('2'),
-        Keycode::Numpad3 => Some('3'),
-        Keycode::Numpad4 => Some('4'),
-        Keycode::Numpad5 => Some('5'),
-        Keycode::Numpad6 => Some('6'),
-        Keycode::Numpad7 => Some('7'),
-        Keycode::Numpad8 => Some('8'),
-        Keycode::Numpad9 => Some('9'),
-        Keycode::NumpadSubtract => Some('-'),
-        Keycode::NumpadAdd => Some('+'),
-        Keycode::NumpadDivide => Some('/'),
-        Keycode::NumpadMultiply => Some('*'),
-        Keycode::Grave => Some('`'),
-        Keycode::Minus => Some('-'),
-        Keycode::Equal => Some('='),
-        Keycode::LeftBracket => Some('['),
-        Keycode::RightBracket => Some(']'),
-        Keycode::BackSlash => Some('\\'),
-        Keycode::Semicolon => Some(':'),
-        Keycode::Apostrophe => Some('\''),
-        Keycode::Comma => Some(','),
-        Keycode::Dot => Some('.'),
-        Keycode::Slash => Some('/'),
-
-        _ => None,
-    }
-}